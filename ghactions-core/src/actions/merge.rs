@@ -0,0 +1,109 @@
+//! # Merge
+
+use super::models::{ActionInput, ActionOutput, ActionYML};
+
+/// Layer one value's fields onto another, filling in anything `self` is
+/// missing from `other` instead of overwriting what is already set.
+///
+/// This lets a base/template action file be overlaid with per-action
+/// overrides, e.g. filling in a missing `description` or unioning `inputs`.
+pub trait Merge {
+    /// Merge `other` into `self`, preferring `self`'s existing values
+    fn merge(&mut self, other: Self);
+}
+
+impl Merge for ActionInput {
+    fn merge(&mut self, other: Self) {
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+        if self.required.is_none() {
+            self.required = other.required;
+        }
+        if self.default.is_none() {
+            self.default = other.default;
+        }
+        if self.deprecation_message.is_none() {
+            self.deprecation_message = other.deprecation_message;
+        }
+    }
+}
+
+impl Merge for ActionOutput {
+    fn merge(&mut self, other: Self) {
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+    }
+}
+
+impl Merge for serde_yaml::Value {
+    /// Union two YAML values: if `self` has nothing of its own (`Null`),
+    /// take `other` wholesale; if both are mappings, keep `self`'s keys
+    /// and fill in any `other` doesn't already have. Anything else (a
+    /// scalar/sequence already set on `self`) is left untouched.
+    fn merge(&mut self, other: Self) {
+        match (self, other) {
+            (self_value @ serde_yaml::Value::Null, other_value) => {
+                *self_value = other_value;
+            }
+            (serde_yaml::Value::Mapping(self_map), serde_yaml::Value::Mapping(other_map)) => {
+                for (key, value) in other_map {
+                    if !self_map.contains_key(&key) {
+                        self_map.insert(key, value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<Metadata> Merge for ActionYML<Metadata>
+where
+    Metadata: Merge,
+{
+    /// Merge `other` into `self`, filling in anything `self` is missing.
+    ///
+    /// `runs` is deliberately left untouched: it's a discriminated union
+    /// describing how the action actually executes (Docker, Node,
+    /// composite, or an unrecognised runtime), and splicing fields from a
+    /// differently-shaped `runs` in would produce a block that doesn't
+    /// correspond to anything real. An overlay that wants to change how
+    /// the action runs should set `runs` directly rather than relying on
+    /// merge to reconcile two of them.
+    fn merge(&mut self, other: Self) {
+        if self.name.is_none() {
+            self.name = other.name;
+        }
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+        if self.author.is_none() {
+            self.author = other.author;
+        }
+        if self.branding.is_none() {
+            self.branding = other.branding;
+        }
+
+        for (name, input) in other.inputs {
+            match self.inputs.get_mut(&name) {
+                Some(existing) => existing.merge(input),
+                None => {
+                    self.inputs.insert(name, input);
+                }
+            }
+        }
+
+        for (name, output) in other.outputs {
+            match self.outputs.get_mut(&name) {
+                Some(existing) => existing.merge(output),
+                None => {
+                    self.outputs.insert(name, output);
+                }
+            }
+        }
+
+        self.metadata.merge(other.metadata);
+    }
+}
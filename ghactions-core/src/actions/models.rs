@@ -3,19 +3,21 @@
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::PathBuf};
 
+use crate::with_path::WithPath;
 use crate::ActionsError;
 
 const GHACTIONS_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
 /// Action YAML file structure
 ///
+/// `Metadata` captures any top-level keys not modelled by the fields below
+/// (either as the default `serde_yaml::Value` catch-all, or a caller-supplied
+/// type) so forward-compatible fields GitHub may add aren't dropped on
+/// [`WithPath::write`].
+///
 /// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct ActionYML {
-    /// Action Path
-    #[serde(skip)]
-    pub path: Option<PathBuf>,
-
+pub struct ActionYML<Metadata = serde_yaml::Value> {
     /// Action Name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -37,12 +39,18 @@ pub struct ActionYML {
 
     /// Action Runs
     pub runs: ActionRuns,
+
+    /// Any other metadata keys not covered above
+    #[serde(flatten)]
+    pub metadata: Metadata,
 }
 
-impl Default for ActionYML {
+impl<Metadata> Default for ActionYML<Metadata>
+where
+    Metadata: Default,
+{
     fn default() -> Self {
         ActionYML {
-            path: None,
             name: Some(env!("CARGO_PKG_NAME").to_string()),
             description: None,
             author: None,
@@ -50,43 +58,66 @@ impl Default for ActionYML {
             inputs: HashMap::new(),
             outputs: HashMap::new(),
             runs: ActionRuns::default(),
+            metadata: Metadata::default(),
         }
     }
 }
 
-impl ActionYML {
+impl<Metadata> ActionYML<Metadata>
+where
+    Metadata: serde::de::DeserializeOwned + Serialize,
+{
+    /// Parse an Action YAML document from an in-memory buffer, e.g. one
+    /// fetched over HTTP or read out of a git blob
+    pub fn from_slice(data: &[u8]) -> Result<Self, ActionsError> {
+        serde_yaml::from_slice(data).map_err(|err| ActionsError::YamlError(err.to_string()))
+    }
+
     /// Load the Action YAML file
-    pub fn load_action(path: String) -> Result<ActionYML, Box<dyn std::error::Error>> {
-        let fhandle = std::fs::File::open(&path)?;
-        let mut action_yml: ActionYML = serde_yaml::from_reader(fhandle)?;
-        action_yml.path = Some(PathBuf::from(path.clone()));
-        Ok(action_yml)
+    pub fn load_action(path: String) -> Result<WithPath<Self>, ActionsError> {
+        let data = std::fs::read(&path).map_err(|err| ActionsError::IOError(err.to_string()))?;
+        let action_yml = Self::from_slice(&data)?;
+        Ok(WithPath::new(path, action_yml))
     }
+}
 
-    /// Write the Action YAML file
-    pub fn write(&self) -> Result<PathBuf, ActionsError> {
-        if let Some(ref path) = self.path {
-            if !path.exists() {
-                let parent = path.parent().unwrap();
-                std::fs::create_dir_all(parent)
-                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
-            }
+impl<Metadata> std::str::FromStr for ActionYML<Metadata>
+where
+    Metadata: serde::de::DeserializeOwned + Serialize,
+{
+    type Err = ActionsError;
 
-            // Create or Open the file
-            let fhandle = std::fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(path)
-                .map_err(|err| ActionsError::IOError(err.to_string()))?;
+    /// Parse an Action YAML document from a string
+    fn from_str(data: &str) -> Result<Self, ActionsError> {
+        serde_yaml::from_str(data).map_err(|err| ActionsError::YamlError(err.to_string()))
+    }
+}
 
-            serde_yaml::to_writer(fhandle, self)
+impl<Metadata> WithPath<ActionYML<Metadata>>
+where
+    Metadata: Serialize,
+{
+    /// Write the Action YAML file back to its originating path
+    pub fn write(&self) -> Result<PathBuf, ActionsError> {
+        let path = self.path();
+        if !path.exists() {
+            let parent = path.parent().unwrap();
+            std::fs::create_dir_all(parent)
                 .map_err(|err| ActionsError::IOError(err.to_string()))?;
-
-            Ok(path.clone())
-        } else {
-            Err(ActionsError::NotImplemented)
         }
+
+        // Create or Open the file
+        let fhandle = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+        serde_yaml::to_writer(fhandle, &**self)
+            .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+        Ok(path.to_path_buf())
     }
 }
 
@@ -131,24 +162,281 @@ pub struct ActionBranding {
 }
 
 /// Action Runs structure
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub struct ActionRuns {
-    /// Action Name
-    pub using: String,
-    /// Docker Image
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub image: Option<PathBuf>,
-    /// Docker Arguments
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub args: Option<Vec<String>>,
+///
+/// The shape of this block is driven entirely by the `using` key, so it is
+/// modelled as an enum instead of a single struct with optional
+/// Docker-only fields. `using` isn't a closed set, though — GitHub has
+/// shipped `node12`/`node16`/`node20` and will keep adding runtimes — so
+/// this can't be a plain `#[serde(tag = "using")]` derive without
+/// rejecting every `using` value it doesn't already know about. Instead
+/// [`Deserialize`]/[`Serialize`] are implemented by hand: known runtimes
+/// decode into their own variant, and anything else falls back to
+/// [`ActionRuns::Unknown`], which keeps the raw `using` value and fields so
+/// the document still round-trips. See
+/// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions#runs-for-docker-container-actions
+#[derive(Debug, PartialEq)]
+pub enum ActionRuns {
+    /// Docker container action
+    Docker {
+        /// Docker Image, e.g. `Dockerfile`, a relative path, or a
+        /// `docker://` registry reference
+        image: PathBuf,
+        /// Docker Arguments
+        args: Option<Vec<String>>,
+        /// Docker Entrypoint override
+        entrypoint: Option<String>,
+        /// Entrypoint run before the `entrypoint` command
+        pre_entrypoint: Option<String>,
+        /// Entrypoint run after the `entrypoint` command, always runs
+        post_entrypoint: Option<String>,
+        /// Environment variables passed to the container
+        env: Option<HashMap<String, String>>,
+    },
+    /// JavaScript action
+    Node {
+        /// The Node runtime this action targets, without the `node`
+        /// prefix (e.g. `"20"` for `using: node20`), so older or newer
+        /// runtimes (`node12`, `node24`, ...) round-trip without needing
+        /// their own variant
+        version: String,
+        /// Entry file run for the action, e.g. `index.js`
+        main: String,
+        /// Script run before `main`
+        pre: Option<String>,
+        /// Condition controlling whether `pre` runs
+        pre_if: Option<String>,
+        /// Script run after `main`, always runs
+        post: Option<String>,
+        /// Condition controlling whether `post` runs
+        post_if: Option<String>,
+    },
+    /// Composite action made up of other steps
+    Composite {
+        /// The steps that make up the composite action
+        steps: Vec<CompositeStep>,
+    },
+    /// A `using` value this crate doesn't recognise yet. Keeps the raw
+    /// `using` tag and every other field exactly as read, so an action
+    /// using a runtime newer than this crate still round-trips through
+    /// [`ActionYML::from_slice`]/`write` instead of failing to parse.
+    Unknown {
+        /// The raw, unrecognised `using` value
+        using: String,
+        /// Every other field under `runs`, preserved verbatim
+        fields: serde_yaml::Mapping,
+    },
 }
 
 impl Default for ActionRuns {
     fn default() -> Self {
-        Self {
-            using: String::from("docker"),
-            image: Some(PathBuf::from("./Dockerfile")),
+        ActionRuns::Docker {
+            image: PathBuf::from("./Dockerfile"),
             args: None,
+            entrypoint: None,
+            pre_entrypoint: None,
+            post_entrypoint: None,
+            env: None,
+        }
+    }
+}
+
+/// On-the-wire shape of a `using: docker` block, minus the `using` tag
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct DockerRun {
+    image: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entrypoint: Option<String>,
+    #[serde(rename = "pre-entrypoint", skip_serializing_if = "Option::is_none")]
+    pre_entrypoint: Option<String>,
+    #[serde(rename = "post-entrypoint", skip_serializing_if = "Option::is_none")]
+    post_entrypoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env: Option<HashMap<String, String>>,
+}
+
+/// On-the-wire shape of a `using: nodeNN` block, minus the `using` tag
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct NodeRun {
+    main: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pre: Option<String>,
+    #[serde(rename = "pre-if", skip_serializing_if = "Option::is_none")]
+    pre_if: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post: Option<String>,
+    #[serde(rename = "post-if", skip_serializing_if = "Option::is_none")]
+    post_if: Option<String>,
+}
+
+/// On-the-wire shape of a `using: composite` block, minus the `using` tag
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CompositeRun {
+    steps: Vec<CompositeStep>,
+}
+
+impl<'de> Deserialize<'de> for ActionRuns {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mapping = serde_yaml::Mapping::deserialize(deserializer)?;
+
+        let mut using = None;
+        let mut fields = serde_yaml::Mapping::new();
+        for (key, value) in mapping {
+            if key.as_str() == Some("using") {
+                using = value.as_str().map(str::to_string);
+            } else {
+                fields.insert(key, value);
+            }
+        }
+        let using = using.ok_or_else(|| serde::de::Error::missing_field("using"))?;
+        let rest = serde_yaml::Value::Mapping(fields.clone());
+
+        match using.as_str() {
+            "docker" => {
+                let docker: DockerRun =
+                    serde_yaml::from_value(rest).map_err(serde::de::Error::custom)?;
+                Ok(ActionRuns::Docker {
+                    image: docker.image,
+                    args: docker.args,
+                    entrypoint: docker.entrypoint,
+                    pre_entrypoint: docker.pre_entrypoint,
+                    post_entrypoint: docker.post_entrypoint,
+                    env: docker.env,
+                })
+            }
+            "composite" => {
+                let composite: CompositeRun =
+                    serde_yaml::from_value(rest).map_err(serde::de::Error::custom)?;
+                Ok(ActionRuns::Composite {
+                    steps: composite.steps,
+                })
+            }
+            _ if using.starts_with("node") => {
+                let node: NodeRun =
+                    serde_yaml::from_value(rest).map_err(serde::de::Error::custom)?;
+                Ok(ActionRuns::Node {
+                    version: using["node".len()..].to_string(),
+                    main: node.main,
+                    pre: node.pre,
+                    pre_if: node.pre_if,
+                    post: node.post,
+                    post_if: node.post_if,
+                })
+            }
+            _ => Ok(ActionRuns::Unknown { using, fields }),
+        }
+    }
+}
+
+impl Serialize for ActionRuns {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut mapping = serde_yaml::Mapping::new();
+
+        match self {
+            ActionRuns::Docker {
+                image,
+                args,
+                entrypoint,
+                pre_entrypoint,
+                post_entrypoint,
+                env,
+            } => {
+                mapping.insert("using".into(), "docker".into());
+                let docker = DockerRun {
+                    image: image.clone(),
+                    args: args.clone(),
+                    entrypoint: entrypoint.clone(),
+                    pre_entrypoint: pre_entrypoint.clone(),
+                    post_entrypoint: post_entrypoint.clone(),
+                    env: env.clone(),
+                };
+                merge_fields(&mut mapping, docker)?;
+            }
+            ActionRuns::Node {
+                version,
+                main,
+                pre,
+                pre_if,
+                post,
+                post_if,
+            } => {
+                mapping.insert("using".into(), format!("node{version}").into());
+                let node = NodeRun {
+                    main: main.clone(),
+                    pre: pre.clone(),
+                    pre_if: pre_if.clone(),
+                    post: post.clone(),
+                    post_if: post_if.clone(),
+                };
+                merge_fields(&mut mapping, node)?;
+            }
+            ActionRuns::Composite { steps } => {
+                mapping.insert("using".into(), "composite".into());
+                merge_fields(
+                    &mut mapping,
+                    CompositeRun {
+                        steps: steps.clone(),
+                    },
+                )?;
+            }
+            ActionRuns::Unknown { using, fields } => {
+                mapping.insert("using".into(), using.clone().into());
+                for (key, value) in fields {
+                    mapping.insert(key.clone(), value.clone());
+                }
+            }
         }
+
+        mapping.serialize(serializer)
     }
 }
+
+/// Flatten a known `runs` shape's fields into `mapping` next to `using`
+fn merge_fields<T, E>(mapping: &mut serde_yaml::Mapping, value: T) -> Result<(), E>
+where
+    T: Serialize,
+    E: serde::ser::Error,
+{
+    if let serde_yaml::Value::Mapping(extra) = serde_yaml::to_value(value).map_err(E::custom)? {
+        for (key, value) in extra {
+            mapping.insert(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// A single step in a `composite` Action Run
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CompositeStep {
+    /// Step Name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Step ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Condition controlling whether the step runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#if: Option<String>,
+    /// Shell command to run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    /// Action reference to run, e.g. `actions/checkout@v4`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uses: Option<String>,
+    /// Inputs passed to a `uses` step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with: Option<HashMap<String, String>>,
+    /// Environment variables for a `run` step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    /// Shell used to execute a `run` step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+}
@@ -0,0 +1,368 @@
+//! # Validate
+
+use regex::Regex;
+
+use super::models::ActionYML;
+use crate::ActionsError;
+
+/// Colors GitHub accepts for an action's `branding.color`
+///
+/// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions#brandingcolor
+pub const VALID_BRANDING_COLORS: &[&str] = &[
+    "white",
+    "yellow",
+    "blue",
+    "green",
+    "orange",
+    "red",
+    "purple",
+    "gray-dark",
+];
+
+/// Feather icon names GitHub accepts for an action's `branding.icon`
+///
+/// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions#brandingicon
+pub const VALID_BRANDING_ICONS: &[&str] = &[
+    "activity",
+    "airplay",
+    "alert-circle",
+    "alert-octagon",
+    "alert-triangle",
+    "align-center",
+    "align-justify",
+    "align-left",
+    "align-right",
+    "anchor",
+    "aperture",
+    "archive",
+    "arrow-down",
+    "arrow-down-circle",
+    "arrow-down-left",
+    "arrow-down-right",
+    "arrow-left",
+    "arrow-left-circle",
+    "arrow-right",
+    "arrow-right-circle",
+    "arrow-up",
+    "arrow-up-circle",
+    "arrow-up-left",
+    "arrow-up-right",
+    "at-sign",
+    "award",
+    "bar-chart",
+    "bar-chart-2",
+    "battery",
+    "battery-charging",
+    "bell",
+    "bell-off",
+    "bluetooth",
+    "bold",
+    "book",
+    "book-open",
+    "bookmark",
+    "box",
+    "briefcase",
+    "calendar",
+    "camera",
+    "camera-off",
+    "cast",
+    "check",
+    "check-circle",
+    "check-square",
+    "chevron-down",
+    "chevron-left",
+    "chevron-right",
+    "chevron-up",
+    "chevrons-down",
+    "chevrons-left",
+    "chevrons-right",
+    "chevrons-up",
+    "circle",
+    "clipboard",
+    "clock",
+    "cloud",
+    "cloud-drizzle",
+    "cloud-lightning",
+    "cloud-off",
+    "cloud-rain",
+    "cloud-snow",
+    "code",
+    "codepen",
+    "codesandbox",
+    "coffee",
+    "columns",
+    "command",
+    "compass",
+    "copy",
+    "corner-down-left",
+    "corner-down-right",
+    "corner-left-down",
+    "corner-left-up",
+    "corner-right-down",
+    "corner-right-up",
+    "corner-up-left",
+    "corner-up-right",
+    "cpu",
+    "credit-card",
+    "crop",
+    "crosshair",
+    "database",
+    "delete",
+    "disc",
+    "divide",
+    "divide-circle",
+    "divide-square",
+    "dollar-sign",
+    "download",
+    "download-cloud",
+    "droplet",
+    "edit",
+    "edit-2",
+    "edit-3",
+    "external-link",
+    "eye",
+    "eye-off",
+    "fast-forward",
+    "feather",
+    "figma",
+    "file",
+    "file-minus",
+    "file-plus",
+    "file-text",
+    "film",
+    "filter",
+    "flag",
+    "folder",
+    "folder-minus",
+    "folder-plus",
+    "framer",
+    "frown",
+    "gift",
+    "git-branch",
+    "git-commit",
+    "git-merge",
+    "git-pull-request",
+    "globe",
+    "grid",
+    "hard-drive",
+    "hash",
+    "headphones",
+    "heart",
+    "help-circle",
+    "hexagon",
+    "home",
+    "image",
+    "inbox",
+    "info",
+    "italic",
+    "key",
+    "layers",
+    "layout",
+    "life-buoy",
+    "link",
+    "link-2",
+    "list",
+    "loader",
+    "lock",
+    "log-in",
+    "log-out",
+    "mail",
+    "map",
+    "map-pin",
+    "maximize",
+    "maximize-2",
+    "meh",
+    "menu",
+    "message-circle",
+    "message-square",
+    "mic",
+    "mic-off",
+    "minimize",
+    "minimize-2",
+    "minus",
+    "minus-circle",
+    "minus-square",
+    "monitor",
+    "moon",
+    "more-horizontal",
+    "more-vertical",
+    "mouse-pointer",
+    "move",
+    "music",
+    "navigation",
+    "navigation-2",
+    "octagon",
+    "package",
+    "paperclip",
+    "pause",
+    "pause-circle",
+    "pen-tool",
+    "percent",
+    "phone",
+    "phone-call",
+    "phone-forwarded",
+    "phone-incoming",
+    "phone-missed",
+    "phone-off",
+    "phone-outgoing",
+    "pie-chart",
+    "play",
+    "play-circle",
+    "plus",
+    "plus-circle",
+    "plus-square",
+    "pocket",
+    "power",
+    "printer",
+    "radio",
+    "refresh-ccw",
+    "refresh-cw",
+    "repeat",
+    "rewind",
+    "rotate-ccw",
+    "rotate-cw",
+    "rss",
+    "save",
+    "scissors",
+    "search",
+    "send",
+    "server",
+    "settings",
+    "share",
+    "share-2",
+    "shield",
+    "shield-off",
+    "shopping-bag",
+    "shopping-cart",
+    "shuffle",
+    "sidebar",
+    "skip-back",
+    "skip-forward",
+    "slash",
+    "sliders",
+    "smartphone",
+    "smile",
+    "speaker",
+    "square",
+    "star",
+    "stop-circle",
+    "sun",
+    "sunrise",
+    "sunset",
+    "tablet",
+    "tag",
+    "target",
+    "terminal",
+    "thermometer",
+    "thumbs-down",
+    "thumbs-up",
+    "toggle-left",
+    "toggle-right",
+    "tool",
+    "trash",
+    "trash-2",
+    "trending-down",
+    "trending-up",
+    "triangle",
+    "truck",
+    "tv",
+    "type",
+    "umbrella",
+    "underline",
+    "unlock",
+    "upload",
+    "upload-cloud",
+    "user",
+    "user-check",
+    "user-minus",
+    "user-plus",
+    "user-x",
+    "users",
+    "video",
+    "video-off",
+    "voicemail",
+    "volume",
+    "volume-1",
+    "volume-2",
+    "volume-x",
+    "watch",
+    "wifi",
+    "wifi-off",
+    "wind",
+    "x",
+    "x-circle",
+    "x-octagon",
+    "x-square",
+    "zap",
+    "zap-off",
+    "zoom-in",
+    "zoom-out",
+];
+
+impl<Metadata> ActionYML<Metadata> {
+    /// Validate the action metadata against the constraints GitHub enforces
+    /// when publishing to the Marketplace, collecting every problem found
+    /// rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<ActionsError>> {
+        let mut errors = Vec::new();
+
+        if self.name.is_none() {
+            errors.push(ActionsError::ValidationError(
+                "`name` is required".to_string(),
+            ));
+        }
+        if self.description.is_none() {
+            errors.push(ActionsError::ValidationError(
+                "`description` is required".to_string(),
+            ));
+        }
+
+        if let Some(branding) = &self.branding {
+            if !VALID_BRANDING_COLORS.contains(&branding.color.as_str()) {
+                errors.push(ActionsError::ValidationError(format!(
+                    "`branding.color` `{}` is not one of {:?}",
+                    branding.color, VALID_BRANDING_COLORS
+                )));
+            }
+            if !VALID_BRANDING_ICONS.contains(&branding.icon.as_str()) {
+                errors.push(ActionsError::ValidationError(format!(
+                    "`branding.icon` `{}` is not a recognised Feather icon",
+                    branding.icon
+                )));
+            }
+        }
+
+        let identifier = Regex::new(r"^[A-Za-z_][A-Za-z0-9_-]*$").expect("static regex is valid");
+
+        for name in self.inputs.keys() {
+            if !identifier.is_match(name) {
+                errors.push(ActionsError::ValidationError(format!(
+                    "input name `{name}` is not a valid identifier"
+                )));
+            }
+        }
+        for name in self.outputs.keys() {
+            if !identifier.is_match(name) {
+                errors.push(ActionsError::ValidationError(format!(
+                    "output name `{name}` is not a valid identifier"
+                )));
+            }
+        }
+
+        for (name, input) in &self.inputs {
+            if let Some(message) = &input.deprecation_message {
+                if message.trim().is_empty() {
+                    errors.push(ActionsError::ValidationError(format!(
+                        "input `{name}` is deprecated but its `deprecationMessage` is empty"
+                    )));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
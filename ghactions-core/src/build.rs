@@ -0,0 +1,180 @@
+//! # Build
+//!
+//! Build the Docker image a `docker` [`crate::actions::models::ActionRuns`]
+//! points at, so a Docker action can be run and tested locally straight
+//! from its `action.yml`.
+
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use ignore::gitignore::GitignoreBuilder;
+
+use crate::actions::models::ActionRuns;
+use crate::ActionsError;
+
+/// How an `ActionRuns::Docker`'s `image` field should be resolved
+#[derive(Debug, PartialEq)]
+pub enum ImageSource {
+    /// `image` is a `Dockerfile` (or a directory containing one) that
+    /// needs to be built
+    Dockerfile(PathBuf),
+    /// `image` is a `docker://` reference to an already-published image;
+    /// it only needs to be pulled and validated, not built
+    Registry(String),
+}
+
+impl ImageSource {
+    /// Classify an `ActionRuns::Docker`'s `image` field
+    pub fn from_image(image: &Path) -> Self {
+        match image.to_string_lossy().strip_prefix("docker://") {
+            Some(reference) => ImageSource::Registry(reference.to_string()),
+            None => ImageSource::Dockerfile(image.to_path_buf()),
+        }
+    }
+}
+
+/// Build (or pull, for a `docker://` reference) the image for a Docker
+/// `ActionRuns`, returning the tag of the image the caller can then run.
+pub async fn build_action_image(
+    runs: &ActionRuns,
+    context: &Path,
+    tag: &str,
+) -> Result<String, ActionsError> {
+    let image = match runs {
+        ActionRuns::Docker { image, .. } => image,
+        _ => {
+            return Err(ActionsError::BuildError(
+                "action does not use `using: docker`".to_string(),
+            ))
+        }
+    };
+
+    match ImageSource::from_image(image) {
+        ImageSource::Registry(reference) => {
+            pull_image(&reference).await?;
+            Ok(reference)
+        }
+        ImageSource::Dockerfile(dockerfile) => {
+            // `image` is relative to the action's directory, not the cwd
+            let dockerfile = context.join(dockerfile);
+            let (build_root, dockerfile_name) = if dockerfile.is_dir() {
+                (dockerfile, PathBuf::from("Dockerfile"))
+            } else {
+                let name = dockerfile
+                    .strip_prefix(context)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(dockerfile);
+                (context.to_path_buf(), name)
+            };
+
+            let tarball = build_context_tarball(&build_root)?;
+            stream_build(tarball, &dockerfile_name, tag).await?;
+            Ok(tag.to_string())
+        }
+    }
+}
+
+/// Tar up the build context, honouring `.dockerignore` if present. Uses a
+/// real gitignore-style matcher (globs, `**`, trailing-slash directories,
+/// `!` negations) rather than literal path-prefix matching, since
+/// `.dockerignore` follows the same syntax as `.gitignore`.
+fn build_context_tarball(context: &Path) -> Result<Vec<u8>, ActionsError> {
+    let mut ignore_builder = GitignoreBuilder::new(context);
+    let dockerignore = context.join(".dockerignore");
+    if dockerignore.exists() {
+        if let Some(err) = ignore_builder.add(&dockerignore) {
+            return Err(ActionsError::IOError(err.to_string()));
+        }
+    }
+    let ignore = ignore_builder
+        .build()
+        .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+    let mut buffer = Vec::new();
+    {
+        let mut builder = tar::Builder::new(&mut buffer);
+        // `filter_entry` prunes descent into an ignored directory entirely,
+        // so its contents are never even visited (and `matched_path_or_any_parents`
+        // catches files whose *parent* directory is what's ignored, e.g.
+        // `node_modules/` or `target/`, not just the file itself).
+        let walker = walkdir::WalkDir::new(context)
+            .into_iter()
+            .filter_entry(|entry| {
+                let Ok(relative) = entry.path().strip_prefix(context) else {
+                    return true;
+                };
+                if relative.as_os_str().is_empty() {
+                    return true;
+                }
+                !ignore
+                    .matched_path_or_any_parents(relative, entry.file_type().is_dir())
+                    .is_ignore()
+            });
+
+        for entry in walker {
+            let entry = entry.map_err(|err| ActionsError::IOError(err.to_string()))?;
+            let relative = entry
+                .path()
+                .strip_prefix(context)
+                .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+
+            if entry.file_type().is_file() {
+                builder
+                    .append_path_with_name(entry.path(), relative)
+                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
+            }
+        }
+        builder
+            .finish()
+            .map_err(|err| ActionsError::IOError(err.to_string()))?;
+    }
+
+    Ok(buffer)
+}
+
+/// Stream the build context to the Docker daemon's build endpoint
+async fn stream_build(tarball: Vec<u8>, dockerfile: &Path, tag: &str) -> Result<(), ActionsError> {
+    let docker = bollard::Docker::connect_with_local_defaults()
+        .map_err(|err| ActionsError::BuildError(err.to_string()))?;
+
+    let options = bollard::image::BuildImageOptions {
+        dockerfile: dockerfile.to_string_lossy().into_owned(),
+        t: tag.to_string(),
+        ..Default::default()
+    };
+
+    let mut stream = docker.build_image(options, None, Some(tarball.into()));
+    while let Some(progress) = stream.next().await {
+        let info = progress.map_err(|err| ActionsError::BuildError(err.to_string()))?;
+        if let Some(error) = info.error {
+            return Err(ActionsError::BuildError(error));
+        }
+    }
+
+    Ok(())
+}
+
+/// Pull (and thereby validate) a `docker://` image reference
+async fn pull_image(reference: &str) -> Result<(), ActionsError> {
+    let docker = bollard::Docker::connect_with_local_defaults()
+        .map_err(|err| ActionsError::BuildError(err.to_string()))?;
+
+    let options = bollard::image::CreateImageOptions {
+        from_image: reference.to_string(),
+        ..Default::default()
+    };
+
+    let mut stream = docker.create_image(Some(options), None, None);
+    while let Some(progress) = stream.next().await {
+        let info = progress.map_err(|err| ActionsError::BuildError(err.to_string()))?;
+        if let Some(error) = info.error {
+            return Err(ActionsError::BuildError(error));
+        }
+    }
+
+    Ok(())
+}
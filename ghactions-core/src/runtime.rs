@@ -0,0 +1,132 @@
+//! # Runtime
+//!
+//! Where [`crate::actions::models`] describes an `action.yml`, this module
+//! is for code running *inside* the action once GitHub's runner has
+//! started it: reading declared inputs and writing outputs back via the
+//! env-file protocol (`$GITHUB_OUTPUT`, `$GITHUB_ENV`, `$GITHUB_PATH`).
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use uuid::Uuid;
+
+use crate::actions::models::ActionYML;
+use crate::ActionsError;
+
+/// Read a single input declared in `action.yml` from its `INPUT_<NAME>`
+/// environment variable, honouring `default` and erroring if the input is
+/// `required` but unset.
+pub fn get_input<Metadata>(
+    action: &ActionYML<Metadata>,
+    name: &str,
+) -> Result<String, ActionsError> {
+    let input = action
+        .inputs
+        .get(name)
+        .ok_or_else(|| ActionsError::InputError(format!("unknown input `{name}`")))?;
+
+    let env_name = input_env_name(name);
+
+    match std::env::var(&env_name) {
+        Ok(value) if !value.is_empty() => Ok(value),
+        _ => {
+            if let Some(default) = &input.default {
+                Ok(default.clone())
+            } else if input.required.unwrap_or(false) {
+                Err(ActionsError::InputError(format!(
+                    "input `{name}` is required but was not set"
+                )))
+            } else {
+                Ok(String::new())
+            }
+        }
+    }
+}
+
+/// Read every input declared in `action.yml` into a map, keyed by name
+pub fn get_inputs<Metadata>(
+    action: &ActionYML<Metadata>,
+) -> Result<HashMap<String, String>, ActionsError> {
+    action
+        .inputs
+        .keys()
+        .map(|name| get_input(action, name).map(|value| (name.clone(), value)))
+        .collect()
+}
+
+/// Write an output, validating the name against the action's declared
+/// `outputs` so typos are caught instead of silently vanishing
+pub fn set_output<Metadata>(
+    action: &ActionYML<Metadata>,
+    name: &str,
+    value: &str,
+) -> Result<(), ActionsError> {
+    if !action.outputs.contains_key(name) {
+        return Err(ActionsError::OutputError(format!(
+            "`{name}` is not a declared output"
+        )));
+    }
+
+    append_env_file("GITHUB_OUTPUT", name, value)
+}
+
+/// Export an environment variable for steps that run after this one
+pub fn set_env(name: &str, value: &str) -> Result<(), ActionsError> {
+    append_env_file("GITHUB_ENV", name, value)
+}
+
+/// Prepend a directory to `PATH` for steps that run after this one
+pub fn add_path(path: &str) -> Result<(), ActionsError> {
+    let file = std::env::var("GITHUB_PATH")
+        .map_err(|_| ActionsError::IOError("GITHUB_PATH is not set".to_string()))?;
+
+    let mut fhandle = std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(file)
+        .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+    writeln!(fhandle, "{path}").map_err(|err| ActionsError::IOError(err.to_string()))
+}
+
+/// Build the `INPUT_<NAME>` environment variable name the runner sets for
+/// a given input, matching `@actions/core`'s `getInput`: only spaces are
+/// replaced with `_` before uppercasing, dashes are left as-is (so
+/// `fetch-depth` becomes `INPUT_FETCH-DEPTH`, not `INPUT_FETCH_DEPTH`).
+pub(crate) fn input_env_name(name: &str) -> String {
+    format!("INPUT_{}", name.replace(' ', "_").to_uppercase())
+}
+
+/// Append a `name=value` pair (or a `name<<delimiter` heredoc for
+/// multiline values) to the env-file named by `env_var`, following the
+/// protocol GitHub's runner uses for `$GITHUB_OUTPUT` / `$GITHUB_ENV`.
+///
+/// A fixed delimiter like `EOF` would let a value containing a line that
+/// happens to read `EOF` corrupt the file (or inject extra `name=value`
+/// pairs), so a fresh random delimiter is generated per write, matching
+/// what GitHub's own runner does. The (vanishingly unlikely) case of the
+/// value still containing that exact delimiter is rejected outright
+/// rather than risked.
+fn append_env_file(env_var: &str, name: &str, value: &str) -> Result<(), ActionsError> {
+    let file = std::env::var(env_var)
+        .map_err(|_| ActionsError::IOError(format!("{env_var} is not set")))?;
+
+    let mut fhandle = std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(file)
+        .map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+    if value.contains('\n') {
+        let delimiter = Uuid::new_v4().to_string();
+        if value.lines().any(|line| line == delimiter) {
+            return Err(ActionsError::OutputError(format!(
+                "value for `{name}` contains the delimiter `{delimiter}`"
+            )));
+        }
+        writeln!(fhandle, "{name}<<{delimiter}\n{value}\n{delimiter}")
+    } else {
+        writeln!(fhandle, "{name}={value}")
+    }
+    .map_err(|err| ActionsError::IOError(err.to_string()))
+}
@@ -0,0 +1,169 @@
+//! # Testing
+//!
+//! Gated behind the `testing` feature. Scaffolds throwaway action
+//! directories and environment so downstream crates can unit-test their
+//! actions against this crate's [`crate::runtime`] without leaking state
+//! between tests.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use tempfile::{NamedTempFile, TempDir};
+
+use crate::actions::models::{ActionRuns, ActionYML};
+use crate::runtime::input_env_name;
+use crate::with_path::WithPath;
+use crate::ActionsError;
+
+/// Serializes `with_inputs`/`with_github_output`/`with_action_env`, since
+/// all three mutate process-global environment variables that would
+/// otherwise race across cargo's default multi-threaded test runner. Not
+/// reentrant — see the warning on each function for what that means for
+/// nesting them.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// A throwaway action directory, cleaned up when dropped
+pub struct TestAction {
+    dir: TempDir,
+}
+
+impl TestAction {
+    /// Write `action` into a fresh temporary directory, alongside a stub
+    /// `Dockerfile`/`entrypoint.sh` or JS entry point matching its
+    /// `runs.using` (composite actions need no extra files of their own).
+    pub fn new<Metadata>(action: ActionYML<Metadata>) -> Result<Self, ActionsError>
+    where
+        Metadata: serde::Serialize,
+    {
+        let dir = tempfile::tempdir().map_err(|err| ActionsError::IOError(err.to_string()))?;
+
+        match &action.runs {
+            ActionRuns::Docker { .. } => {
+                std::fs::write(dir.path().join("Dockerfile"), "FROM alpine:latest\n")
+                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
+                std::fs::write(dir.path().join("entrypoint.sh"), "#!/bin/sh\nset -e\n")
+                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
+            }
+            ActionRuns::Node { main, .. } => {
+                std::fs::write(dir.path().join(main), "// stub entry point\n")
+                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
+            }
+            ActionRuns::Composite { .. } | ActionRuns::Unknown { .. } => {}
+        }
+
+        let action_yml = WithPath::new(dir.path().join("action.yml"), action);
+        action_yml.write()?;
+
+        Ok(Self { dir })
+    }
+
+    /// The path of the scaffolded action directory
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Run `func` with `INPUT_<NAME>` set for each entry in `inputs`, clearing
+/// them again afterwards so tests don't leak environment into each other.
+/// Holds [`ENV_LOCK`] for the duration of `func`, since this mutates
+/// process-global environment variables.
+///
+/// `ENV_LOCK` isn't reentrant: don't call [`with_github_output`] (or this
+/// function again) from inside `func`, or the nested call will deadlock
+/// waiting on a lock this thread already holds. To test inputs and
+/// `$GITHUB_OUTPUT` together, use [`with_action_env`] instead, which takes
+/// the lock once for both.
+pub fn with_inputs<F, R>(inputs: &HashMap<String, String>, func: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let env_names = set_inputs(inputs);
+    let result = func();
+    unset_inputs(&env_names);
+
+    result
+}
+
+/// Run `func` with `$GITHUB_OUTPUT` pointed at a fresh temp file, returning
+/// the function's result alongside the file's final contents. Holds
+/// [`ENV_LOCK`] for the duration of `func`, since this mutates
+/// process-global environment variables.
+///
+/// `ENV_LOCK` isn't reentrant: don't call [`with_inputs`] (or this function
+/// again) from inside `func`, or the nested call will deadlock waiting on
+/// a lock this thread already holds. To test inputs and `$GITHUB_OUTPUT`
+/// together, use [`with_action_env`] instead, which takes the lock once
+/// for both.
+pub fn with_github_output<F, R>(func: F) -> Result<(R, String), ActionsError>
+where
+    F: FnOnce() -> R,
+{
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let file = set_github_output()?;
+    let result = func();
+    let contents = read_github_output(&file)?;
+
+    Ok((result, contents))
+}
+
+/// Run `func` with `INPUT_<NAME>` set for each entry in `inputs` and
+/// `$GITHUB_OUTPUT` pointed at a fresh temp file, taking [`ENV_LOCK`] only
+/// once for both. This is the helper to reach for when testing an action
+/// end-to-end — combining [`with_inputs`] and [`with_github_output`]
+/// instead would re-lock `ENV_LOCK` on the same thread and deadlock.
+pub fn with_action_env<F, R>(
+    inputs: &HashMap<String, String>,
+    func: F,
+) -> Result<(R, String), ActionsError>
+where
+    F: FnOnce() -> R,
+{
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let env_names = set_inputs(inputs);
+    let file = set_github_output()?;
+    let result = func();
+    let contents = read_github_output(&file)?;
+    unset_inputs(&env_names);
+
+    Ok((result, contents))
+}
+
+/// Set `INPUT_<NAME>` for every entry in `inputs`, returning the env var
+/// names that were set so the caller can clear them again
+fn set_inputs(inputs: &HashMap<String, String>) -> Vec<String> {
+    let env_names: Vec<String> = inputs.keys().map(|name| input_env_name(name)).collect();
+
+    for (name, value) in inputs {
+        std::env::set_var(input_env_name(name), value);
+    }
+
+    env_names
+}
+
+/// Clear environment variables previously set by [`set_inputs`]
+fn unset_inputs(env_names: &[String]) {
+    for env_name in env_names {
+        std::env::remove_var(env_name);
+    }
+}
+
+/// Point `$GITHUB_OUTPUT` at a fresh temp file
+fn set_github_output() -> Result<NamedTempFile, ActionsError> {
+    let file = NamedTempFile::new().map_err(|err| ActionsError::IOError(err.to_string()))?;
+    std::env::set_var("GITHUB_OUTPUT", file.path());
+    Ok(file)
+}
+
+/// Read back the `$GITHUB_OUTPUT` temp file set by [`set_github_output`],
+/// then unset `$GITHUB_OUTPUT`
+fn read_github_output(file: &NamedTempFile) -> Result<String, ActionsError> {
+    let contents = std::fs::read_to_string(file.path())
+        .map_err(|err| ActionsError::IOError(err.to_string()))?;
+    std::env::remove_var("GITHUB_OUTPUT");
+    Ok(contents)
+}
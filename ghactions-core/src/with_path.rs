@@ -0,0 +1,46 @@
+//! # With Path
+
+use std::path::{Path, PathBuf};
+
+/// Wraps a value together with the filesystem path it was loaded from (or
+/// is destined to be written to), so the value itself doesn't need an
+/// `Option<PathBuf>` field just to remember where it came from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct WithPath<T> {
+    path: PathBuf,
+    value: T,
+}
+
+impl<T> WithPath<T> {
+    /// Wrap a value together with the path it is associated with
+    pub fn new(path: impl Into<PathBuf>, value: T) -> Self {
+        Self {
+            path: path.into(),
+            value,
+        }
+    }
+
+    /// The path this value is associated with
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Unwrap into the path and the wrapped value
+    pub fn into_inner(self) -> (PathBuf, T) {
+        (self.path, self.value)
+    }
+}
+
+impl<T> std::ops::Deref for WithPath<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> std::ops::DerefMut for WithPath<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}